@@ -1,14 +1,50 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Write};
-use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
-use tauri::State;
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
 
-// Shared state for Python process
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+const RESTART_BASE_BACKOFF: Duration = Duration::from_secs(1);
+const RESTART_MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_CONSECUTIVE_DECODE_ERRORS: u32 = 5;
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Shared state for the Python backend process. Requests are correlated to
+// responses by id rather than by read order, so `stdin`/`pending` can be
+// used from any command concurrently while a single reader thread owns
+// `stdout`. Managed behind an `Arc` so the supervisor can hold a handle to
+// it from a background thread and respawn the child without a command
+// being in flight.
 struct Backend {
-    process: Mutex<Option<Child>>,
+    child: Mutex<Option<Child>>,
+    stdin: Mutex<Option<ChildStdin>>,
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, mpsc::Sender<JsonRpcMessage>>>,
+    project_root: Mutex<Option<PathBuf>>,
+    indexed_documents: Mutex<Vec<Document>>,
+    last_stderr: Mutex<Vec<String>>,
+    restart_attempts: AtomicU32,
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    watched_documents: Mutex<HashMap<String, Document>>,
+    pending_watch_events: Mutex<HashMap<String, (Instant, WatchAction)>>,
+    app_handle: Mutex<Option<AppHandle>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchAction {
+    Reindex,
+    Remove,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -40,187 +76,489 @@ struct AnswerResponse {
     confidence: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct BackendRequest {
-    command: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    question: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    paths: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest {
+    jsonrpc: &'static str,
+    id: u64,
+    method: String,
+    params: serde_json::Value,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct BackendResponse {
-    success: bool,
-    data: Option<serde_json::Value>,
-    error: Option<String>,
-}
-
-fn send_to_backend(backend: &State<Backend>, request: BackendRequest) -> Result<BackendResponse, String> {
-    let mut proc_guard = backend.process.lock().map_err(|e| e.to_string())?;
-    
-    let child = proc_guard.as_mut().ok_or("Backend not started")?;
-    
-    // Write request
-    let stdin = child.stdin.as_mut().ok_or("No stdin")?;
-    let request_json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-    writeln!(stdin, "{}", request_json).map_err(|e| e.to_string())?;
-    stdin.flush().map_err(|e| e.to_string())?;
-    
-    // Read response
-    let stdout = child.stdout.as_mut().ok_or("No stdout")?;
-    let mut reader = BufReader::new(stdout);
-    let mut line = String::new();
-    reader.read_line(&mut line).map_err(|e| e.to_string())?;
-    
-    serde_json::from_str(&line).map_err(|e| format!("Parse error: {} - Response: {}", e, line))
+// A line from the backend is either a reply to one of our requests (has
+// `id`) or a notification (has `method` instead) - e.g. a streamed answer
+// token. Both shapes are modeled by one struct since JSON-RPC 2.0 messages
+// share a single envelope and we only need to branch on which fields are
+// present.
+#[derive(Debug, Deserialize)]
+struct JsonRpcMessage {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    params: Option<serde_json::Value>,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<JsonRpcError>,
 }
 
-#[tauri::command]
-fn init_backend(backend: State<Backend>) -> Result<InitResponse, String> {
-    // Start Python process
-    let project_root = std::env::current_dir()
-        .map_err(|e| e.to_string())?
-        .parent()
-        .ok_or("No parent dir")?
-        .parent()
-        .ok_or("No grandparent dir")?
-        .to_path_buf();
-    
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    #[allow(dead_code)]
+    code: i32,
+    message: String,
+}
+
+fn config_file_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("indexed_documents.json"))
+}
+
+fn load_indexed_documents(app_handle: &AppHandle) -> Vec<Document> {
+    let Ok(path) = config_file_path(app_handle) else {
+        return Vec::new();
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_indexed_documents(app_handle: &AppHandle, documents: &[Document]) {
+    let Ok(path) = config_file_path(app_handle) else {
+        return;
+    };
+    if let Ok(json) = serde_json::to_string_pretty(documents) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn spawn_child(project_root: &Path) -> Result<Child, String> {
     let script_path = project_root.join("backend_server.py");
-    
     println!("Starting backend: python3 {} --json-mode", script_path.display());
-    
-    let child = Command::new("python3")
+    Command::new("python3")
         .arg(&script_path)
         .arg("--json-mode")
-        .current_dir(&project_root)
+        .current_dir(project_root)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
+        .stderr(Stdio::piped())
         .spawn()
-        .map_err(|e| format!("Failed to start Python: {}", e))?;
-    
-    // Store process
-    {
-        let mut proc = backend.process.lock().map_err(|e| e.to_string())?;
-        *proc = Some(child);
+        .map_err(|e| format!("Failed to start Python: {}", e))
+}
+
+// Forwards the child's stderr to our own for debugging and keeps the last
+// few lines around so a crash report can include them.
+fn spawn_stderr_thread(stderr: ChildStderr, backend: Arc<Backend>) {
+    std::thread::spawn(move || {
+        let reader = BufReader::new(stderr);
+        for line in reader.lines() {
+            let Ok(line) = line else { break };
+            eprintln!("[backend] {}", line);
+            let mut last_stderr = backend.last_stderr.lock().unwrap();
+            last_stderr.push(line);
+            let keep = 20;
+            if last_stderr.len() > keep {
+                let drop_count = last_stderr.len() - keep;
+                last_stderr.drain(0..drop_count);
+            }
+        }
+    });
+}
+
+// Reads stdout for the lifetime of the child, dispatching each line either
+// to the `pending` sender waiting on its id or, for id-less notifications,
+// to a Tauri event named after the method. A broken pipe, EOF, or a run of
+// undecodable lines is treated as the backend having crashed and hands off
+// to the supervisor to restart it.
+fn spawn_reader_thread(stdout: ChildStdout, backend: Arc<Backend>, app_handle: AppHandle) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut consecutive_decode_errors = 0u32;
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+
+            let message: JsonRpcMessage = match serde_json::from_str(&line) {
+                Ok(message) => {
+                    consecutive_decode_errors = 0;
+                    message
+                }
+                Err(_) => {
+                    consecutive_decode_errors += 1;
+                    if consecutive_decode_errors >= MAX_CONSECUTIVE_DECODE_ERRORS {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            match message.id {
+                Some(id) => {
+                    if let Some(sender) = backend.pending.lock().unwrap().remove(&id) {
+                        let _ = sender.send(message);
+                    }
+                }
+                None => {
+                    if let Some(method) = &message.method {
+                        let _ = app_handle.emit(method, message.params.clone());
+                    }
+                }
+            }
+        }
+
+        supervise_restart(backend, app_handle);
+    });
+}
+
+// Sends a JSON-RPC request and blocks (with a timeout) until the reader
+// thread delivers the matching response, allowing this call to run
+// concurrently with other in-flight requests on the same backend.
+fn send_request(backend: &Backend, method: &str, params: serde_json::Value) -> Result<serde_json::Value, String> {
+    let id = backend.next_id.fetch_add(1, Ordering::SeqCst);
+    let (tx, rx) = mpsc::channel();
+    backend.pending.lock().map_err(|e| e.to_string())?.insert(id, tx);
+
+    let write_result = (|| -> Result<(), String> {
+        let mut stdin_guard = backend.stdin.lock().map_err(|e| e.to_string())?;
+        let stdin = stdin_guard.as_mut().ok_or("Backend not started")?;
+        let request_json = serde_json::to_string(&JsonRpcRequest {
+            jsonrpc: "2.0",
+            id,
+            method: method.to_string(),
+            params,
+        }).map_err(|e| e.to_string())?;
+        writeln!(stdin, "{}", request_json).map_err(|e| e.to_string())?;
+        stdin.flush().map_err(|e| e.to_string())
+    })();
+
+    if let Err(err) = write_result {
+        backend.pending.lock().map_err(|e| e.to_string())?.remove(&id);
+        return Err(err);
     }
-    
-    // Send init command
-    let response = send_to_backend(&backend, BackendRequest {
-        command: "init".to_string(),
-        question: None,
-        paths: None,
-        name: None,
+
+    let message = rx.recv_timeout(REQUEST_TIMEOUT).map_err(|err| {
+        backend.pending.lock().ok().map(|mut pending| pending.remove(&id));
+        match err {
+            mpsc::RecvTimeoutError::Timeout => format!("Timed out waiting for a response to '{}'", method),
+            mpsc::RecvTimeoutError::Disconnected => format!("Backend restarted before answering '{}'", method),
+        }
     })?;
-    
-    if response.success {
-        let data = response.data.unwrap_or(serde_json::json!({}));
-        let documents: Vec<Document> = serde_json::from_value(
-            data.get("documents").cloned().unwrap_or(serde_json::json!([]))
-        ).unwrap_or_default();
-        
-        Ok(InitResponse {
-            ready: true,
-            documents,
-        })
-    } else {
-        Err(response.error.unwrap_or("Init failed".to_string()))
+
+    if let Some(error) = message.error {
+        return Err(error.message);
     }
+    Ok(message.result.unwrap_or(serde_json::json!({})))
 }
 
-#[tauri::command]
-fn add_documents(paths: Vec<String>, backend: State<Backend>) -> Result<Vec<Document>, String> {
-    let response = send_to_backend(&backend, BackendRequest {
-        command: "add_documents".to_string(),
-        question: None,
-        paths: Some(paths),
-        name: None,
-    })?;
-    
-    if response.success {
-        let data = response.data.unwrap_or(serde_json::json!({}));
-        let documents: Vec<Document> = serde_json::from_value(
-            data.get("documents").cloned().unwrap_or(serde_json::json!([]))
+// Kills and reaps whatever child this `Backend` was previously tracking,
+// if any, so replacing it never leaves the old process running (possible
+// after the decode-error crash signal, where the child may still be alive)
+// or leaves it unreaped as a zombie (the ordinary EOF-crash case).
+fn kill_previous_child(backend: &Backend) -> Result<(), String> {
+    let mut child_guard = backend.child.lock().map_err(|e| e.to_string())?;
+    if let Some(mut child) = child_guard.take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    Ok(())
+}
+
+// Spawns the child, wires up its pipes, and replays `init` plus any
+// previously indexed documents so the backend comes back the way it was
+// before it died (or, on the very first boot, the way it was before the
+// app was last closed).
+fn start_backend(app_handle: AppHandle, backend: Arc<Backend>, project_root: &Path) -> Result<Vec<Document>, String> {
+    kill_previous_child(&backend)?;
+
+    let mut child = spawn_child(project_root)?;
+    let stdin = child.stdin.take().ok_or("No stdin")?;
+    let stdout = child.stdout.take().ok_or("No stdout")?;
+    let stderr = child.stderr.take().ok_or("No stderr")?;
+
+    *backend.stdin.lock().map_err(|e| e.to_string())? = Some(stdin);
+    *backend.child.lock().map_err(|e| e.to_string())? = Some(child);
+    // Requests left over from a dead process can never be answered; drop
+    // them so callers see a clean error instead of hanging until timeout.
+    backend.pending.lock().map_err(|e| e.to_string())?.clear();
+
+    spawn_stderr_thread(stderr, backend.clone());
+    spawn_reader_thread(stdout, backend.clone(), app_handle);
+
+    let init_result = send_request(&backend, "init", serde_json::json!({}))?;
+    let mut documents: Vec<Document> = serde_json::from_value(
+        init_result.get("documents").cloned().unwrap_or(serde_json::json!([]))
+    ).unwrap_or_default();
+
+    let indexed_paths: Vec<String> = backend.indexed_documents.lock().map_err(|e| e.to_string())?
+        .iter()
+        .map(|doc| doc.path.clone())
+        .collect();
+    if !indexed_paths.is_empty() {
+        let add_result = send_request(&backend, "add_documents", serde_json::json!({ "paths": indexed_paths }))?;
+        documents = serde_json::from_value(
+            add_result.get("documents").cloned().unwrap_or(serde_json::json!([]))
         ).unwrap_or_default();
-        Ok(documents)
-    } else {
-        Err(response.error.unwrap_or("Failed to add documents".to_string()))
     }
+
+    *backend.indexed_documents.lock().map_err(|e| e.to_string())? = documents.clone();
+    Ok(documents)
 }
 
-#[tauri::command]
-fn ask_question(question: String, backend: State<Backend>) -> Result<AnswerResponse, String> {
-    let response = send_to_backend(&backend, BackendRequest {
-        command: "ask".to_string(),
-        question: Some(question),
-        paths: None,
-        name: None,
-    })?;
-    
-    if response.success {
-        let data = response.data.unwrap_or(serde_json::json!({}));
-        Ok(AnswerResponse {
-            answer: data.get("answer")
-                .and_then(|v| v.as_str())
-                .unwrap_or("No answer")
-                .to_string(),
-            sources: serde_json::from_value(
-                data.get("sources").cloned().unwrap_or(serde_json::json!([]))
-            ).unwrap_or_default(),
-            confidence: data.get("confidence")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0),
-        })
-    } else {
-        Err(response.error.unwrap_or("Failed to get answer".to_string()))
+// Runs on the (now-dead) reader thread after its loop exits. Retries with
+// bounded exponential backoff, emitting `backend-status` events so the UI
+// can show what's happening, and gives up after `MAX_RESTART_ATTEMPTS`.
+fn supervise_restart(backend: Arc<Backend>, app_handle: AppHandle) {
+    loop {
+        let attempt = backend.restart_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt > MAX_RESTART_ATTEMPTS {
+            let last_stderr = backend.last_stderr.lock().unwrap().join("\n");
+            let _ = app_handle.emit("backend-status", serde_json::json!({
+                "status": "failed",
+                "stderr": last_stderr,
+            }));
+            return;
+        }
+
+        let _ = app_handle.emit("backend-status", serde_json::json!({ "status": "restarting" }));
+
+        let backoff = std::cmp::min(RESTART_BASE_BACKOFF * 2u32.pow(attempt - 1), RESTART_MAX_BACKOFF);
+        std::thread::sleep(backoff);
+
+        let Some(project_root) = backend.project_root.lock().unwrap().clone() else {
+            return;
+        };
+
+        match start_backend(app_handle.clone(), backend.clone(), &project_root) {
+            Ok(_) => {
+                backend.restart_attempts.store(0, Ordering::SeqCst);
+                let _ = app_handle.emit("backend-status", serde_json::json!({ "status": "ready" }));
+                return;
+            }
+            Err(_) => continue,
+        }
+    }
+}
+
+// Starts watching a document's path and records its metadata so a later
+// delete event can be reported back to the backend by name.
+fn register_watch(backend: &Arc<Backend>, document: &Document) {
+    if let Some(watcher) = backend.watcher.lock().unwrap().as_mut() {
+        let _ = watcher.watch(Path::new(&document.path), RecursiveMode::NonRecursive);
+    }
+    backend.watched_documents.lock().unwrap().insert(document.path.clone(), document.clone());
+}
+
+fn unregister_watch(backend: &Arc<Backend>, path: &str) {
+    if let Some(watcher) = backend.watcher.lock().unwrap().as_mut() {
+        let _ = watcher.unwatch(Path::new(path));
+    }
+    backend.watched_documents.lock().unwrap().remove(path);
+}
+
+fn unregister_watch_by_name(backend: &Arc<Backend>, name: &str) {
+    let path = backend.watched_documents.lock().unwrap()
+        .values()
+        .find(|doc| doc.name == name)
+        .map(|doc| doc.path.clone());
+    if let Some(path) = path {
+        unregister_watch(backend, &path);
+    }
+}
+
+// Creates the `notify` watcher and its debounce thread. The watcher's own
+// callback only records *that* a path changed and *how* (modified vs.
+// removed); the debounce thread is what collapses a burst of events for
+// the same path (an editor's save-as-temp-then-rename dance, for example)
+// into a single reindex.
+fn create_watcher(backend: Arc<Backend>) -> Result<RecommendedWatcher, String> {
+    let watch_backend = backend.clone();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        let action = if matches!(event.kind, notify::EventKind::Remove(_)) {
+            WatchAction::Remove
+        } else {
+            WatchAction::Reindex
+        };
+
+        let now = Instant::now();
+        let mut pending = watch_backend.pending_watch_events.lock().unwrap();
+        for path in event.paths {
+            pending.insert(path.to_string_lossy().to_string(), (now, action));
+        }
+    }).map_err(|e| e.to_string())?;
+
+    spawn_watch_debounce_thread(backend);
+    Ok(watcher)
+}
+
+fn spawn_watch_debounce_thread(backend: Arc<Backend>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+
+        let due: Vec<(String, WatchAction)> = {
+            let mut pending = backend.pending_watch_events.lock().unwrap();
+            let now = Instant::now();
+            let due_paths: Vec<String> = pending.iter()
+                .filter(|(_, (seen_at, _))| now.duration_since(*seen_at) >= WATCH_DEBOUNCE)
+                .map(|(path, _)| path.clone())
+                .collect();
+            due_paths.into_iter()
+                .filter_map(|path| pending.remove(&path).map(|(_, action)| (path, action)))
+                .collect()
+        };
+
+        for (path, action) in due {
+            reindex_watched_path(&backend, path, action);
+        }
+    });
+}
+
+// Replays the add/remove that a debounced filesystem event implies, then
+// tells the frontend about whatever the backend now considers current.
+fn reindex_watched_path(backend: &Arc<Backend>, path: String, action: WatchAction) {
+    let Some(app_handle) = backend.app_handle.lock().unwrap().clone() else {
+        return;
+    };
+
+    let response = match action {
+        WatchAction::Remove => {
+            let name = backend.watched_documents.lock().unwrap().get(&path).map(|doc| doc.name.clone());
+            let Some(name) = name else { return };
+            send_request(backend, "remove_document", serde_json::json!({ "name": name }))
+        }
+        WatchAction::Reindex => {
+            send_request(backend, "add_documents", serde_json::json!({ "paths": [path] }))
+        }
+    };
+
+    // Only stop watching once the backend has actually confirmed the
+    // removal - if the RPC failed (e.g. the backend is mid-restart), the
+    // file must stay watched or it silently falls out of the live set.
+    let Ok(result) = response else { return };
+    if action == WatchAction::Remove {
+        unregister_watch(backend, &path);
+    }
+
+    let documents: Vec<Document> = serde_json::from_value(
+        result.get("documents").cloned().unwrap_or(serde_json::json!([]))
+    ).unwrap_or_default();
+
+    for doc in &documents {
+        register_watch(backend, doc);
     }
+    *backend.indexed_documents.lock().unwrap() = documents.clone();
+    save_indexed_documents(&app_handle, &documents);
+    let _ = app_handle.emit("documents-changed", &documents);
 }
 
 #[tauri::command]
-fn remove_document(name: String, backend: State<Backend>) -> Result<(), String> {
-    let response = send_to_backend(&backend, BackendRequest {
-        command: "remove_document".to_string(),
-        question: None,
-        paths: None,
-        name: Some(name),
-    })?;
-    
-    if response.success {
-        Ok(())
-    } else {
-        Err(response.error.unwrap_or("Failed to remove".to_string()))
+fn init_backend(app_handle: AppHandle, backend: State<'_, Arc<Backend>>) -> Result<InitResponse, String> {
+    let project_root = std::env::current_dir()
+        .map_err(|e| e.to_string())?
+        .parent()
+        .ok_or("No parent dir")?
+        .parent()
+        .ok_or("No grandparent dir")?
+        .to_path_buf();
+
+    *backend.app_handle.lock().map_err(|e| e.to_string())? = Some(app_handle.clone());
+    *backend.project_root.lock().map_err(|e| e.to_string())? = Some(project_root.clone());
+    *backend.indexed_documents.lock().map_err(|e| e.to_string())? = load_indexed_documents(&app_handle);
+    // A fresh, explicit start (e.g. the user hitting "retry" after the
+    // supervisor gave up) deserves its own full run of bounded backoff
+    // retries, not whatever count a previous crash loop left behind.
+    backend.restart_attempts.store(0, Ordering::SeqCst);
+
+    {
+        let mut watcher_guard = backend.watcher.lock().map_err(|e| e.to_string())?;
+        if watcher_guard.is_none() {
+            *watcher_guard = Some(create_watcher(backend.inner().clone())?);
+        }
+    }
+
+    let _ = app_handle.emit("backend-status", serde_json::json!({ "status": "starting" }));
+
+    let documents = start_backend(app_handle.clone(), backend.inner().clone(), &project_root)?;
+
+    let _ = app_handle.emit("backend-status", serde_json::json!({ "status": "ready" }));
+
+    for doc in &documents {
+        register_watch(&backend, doc);
     }
+
+    Ok(InitResponse { ready: true, documents })
 }
 
+// The backend emits `ingest-progress` notifications per file while this is
+// in flight; those are forwarded to the frontend as they arrive by the
+// same reader thread that will eventually deliver this call's reply, so
+// there's nothing to loop over here - just wait for the result.
 #[tauri::command]
-fn record_and_transcribe(backend: State<Backend>) -> Result<String, String> {
-    let response = send_to_backend(&backend, BackendRequest {
-        command: "voice_input".to_string(),
-        question: None,
-        paths: None,
-        name: None,
-    })?;
-    
-    if response.success {
-        let data = response.data.unwrap_or(serde_json::json!({}));
-        Ok(data.get("transcription")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string())
-    } else {
-        Err(response.error.unwrap_or("Voice input failed".to_string()))
+fn add_documents(app_handle: AppHandle, paths: Vec<String>, backend: State<'_, Arc<Backend>>) -> Result<Vec<Document>, String> {
+    let result = send_request(&backend, "add_documents", serde_json::json!({ "paths": paths }))?;
+    let documents: Vec<Document> = serde_json::from_value(
+        result.get("documents").cloned().unwrap_or(serde_json::json!([]))
+    ).unwrap_or_default();
+
+    for doc in &documents {
+        register_watch(&backend, doc);
     }
+    *backend.indexed_documents.lock().map_err(|e| e.to_string())? = documents.clone();
+    save_indexed_documents(&app_handle, &documents);
+
+    Ok(documents)
+}
+
+#[tauri::command]
+fn ask_question(question: String, backend: State<'_, Arc<Backend>>) -> Result<AnswerResponse, String> {
+    let result = send_request(&backend, "ask", serde_json::json!({ "question": question }))?;
+    serde_json::from_value(result).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn remove_document(app_handle: AppHandle, name: String, backend: State<'_, Arc<Backend>>) -> Result<(), String> {
+    send_request(&backend, "remove_document", serde_json::json!({ "name": name }))?;
+
+    unregister_watch_by_name(&backend, &name);
+
+    let mut indexed = backend.indexed_documents.lock().map_err(|e| e.to_string())?;
+    indexed.retain(|doc| doc.name != name);
+    save_indexed_documents(&app_handle, &indexed);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn record_and_transcribe(backend: State<'_, Arc<Backend>>) -> Result<String, String> {
+    let result = send_request(&backend, "voice_input", serde_json::json!({}))?;
+    Ok(result.get("transcription")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string())
 }
 
 fn main() {
     tauri::Builder::default()
-        .manage(Backend {
-            process: Mutex::new(None),
-        })
+        .manage(Arc::new(Backend {
+            child: Mutex::new(None),
+            stdin: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+            project_root: Mutex::new(None),
+            indexed_documents: Mutex::new(Vec::new()),
+            last_stderr: Mutex::new(Vec::new()),
+            restart_attempts: AtomicU32::new(0),
+            watcher: Mutex::new(None),
+            watched_documents: Mutex::new(HashMap::new()),
+            pending_watch_events: Mutex::new(HashMap::new()),
+            app_handle: Mutex::new(None),
+        }))
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
@@ -233,4 +571,4 @@ fn main() {
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}